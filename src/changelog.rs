@@ -0,0 +1,235 @@
+//! `changelog` subcommand: build a Keep-a-Changelog-style `CHANGELOG.md` from
+//! conventional commit history.
+//!
+//! The git history is walked with [`git2::Repository`] and each commit subject
+//! is parsed with [`crate::lint::Conventional`], the same parser the lint
+//! subsystem uses, so the message generator and the changelog generator agree
+//! on what a conventional commit is.
+
+use crate::lint::Conventional;
+use colored::Colorize;
+use git2::Repository;
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs;
+
+/// Options controlling changelog generation.
+pub struct ChangelogOptions {
+    /// Lower bound ref/tag (exclusive). When set, only commits in `from..to`
+    /// are considered, enabling incremental updates between two tags.
+    pub from: Option<String>,
+    /// Upper bound ref/tag. Defaults to `HEAD` and names the rendered section.
+    pub to: Option<String>,
+    /// Output path for the rendered changelog.
+    pub output: String,
+}
+
+/// Conventional types mapped to their changelog section headings, in render
+/// order. Types absent from this table are folded under "Other Changes".
+const SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("refactor", "Refactoring"),
+    ("docs", "Documentation"),
+    ("build", "Build System"),
+    ("ci", "Continuous Integration"),
+];
+
+const OTHER_HEADING: &str = "Other Changes";
+
+const HEADER: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Conventional Commits](https://www.conventionalcommits.org/).
+";
+
+const FOOTER: &str =
+    "Generated by [msg](https://github.com/jmvldz/msg) from conventional commit history.";
+
+/// A single rendered changelog entry.
+struct Entry {
+    heading: &'static str,
+    scope: Option<String>,
+    description: String,
+    breaking: bool,
+}
+
+/// Generate the changelog and write it to the configured output path.
+pub fn run(opts: &ChangelogOptions) -> Result<(), Box<dyn Error>> {
+    let repo = Repository::open(".")?;
+    let entries = collect_entries(&repo, opts)?;
+
+    if entries.is_empty() {
+        println!("{}", "No conventional commits found in range".yellow().bold());
+        return Ok(());
+    }
+
+    let date = tip_date(&repo, opts)?;
+    let section = render(&entries, opts, &date);
+
+    // Maintain the file: prepend the new section above any existing releases
+    // rather than clobbering them, so prior history is preserved.
+    let document = match fs::read_to_string(&opts.output) {
+        Ok(existing) => merge(&existing, &section),
+        Err(_) => format!("{HEADER}{section}\n{FOOTER}\n"),
+    };
+    fs::write(&opts.output, document)?;
+    println!(
+        "{} {}",
+        "✅ Wrote changelog to".green().bold(),
+        opts.output.bright_white()
+    );
+    Ok(())
+}
+
+/// Walk the selected revision range and collect the conventional commits.
+fn collect_entries(
+    repo: &Repository,
+    opts: &ChangelogOptions,
+) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let mut revwalk = repo.revwalk()?;
+    match (&opts.from, &opts.to) {
+        (Some(from), to) => {
+            let to = to.as_deref().unwrap_or("HEAD");
+            revwalk.push_range(&format!("{from}..{to}"))?;
+        }
+        (None, Some(to)) => {
+            let object = repo.revparse_single(to)?;
+            revwalk.push(object.id())?;
+        }
+        (None, None) => revwalk.push_head()?,
+    }
+
+    let mut entries = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        let Some(conventional) = commit.summary().and_then(Conventional::parse) else {
+            continue;
+        };
+
+        let heading = SECTIONS
+            .iter()
+            .find(|(ty, _)| *ty == conventional.type_)
+            .map(|(_, heading)| *heading)
+            .unwrap_or(OTHER_HEADING);
+
+        // A breaking change is flagged by the `!` marker or a body footer.
+        let breaking = conventional.breaking
+            || commit.body().is_some_and(|b| b.contains("BREAKING CHANGE:"));
+
+        entries.push(Entry {
+            heading,
+            scope: conventional.scope,
+            description: conventional.description,
+            breaking,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Resolve the release date (`YYYY-MM-DD`) from the committer time of the tip
+/// commit named by `to` (or `HEAD`), so the section header carries the date the
+/// release was actually cut.
+fn tip_date(repo: &Repository, opts: &ChangelogOptions) -> Result<String, Box<dyn Error>> {
+    let tip = opts.to.as_deref().unwrap_or("HEAD");
+    let commit = repo.revparse_single(tip)?.peel_to_commit()?;
+    Ok(format_date(commit.time().seconds()))
+}
+
+/// Format a UTC Unix timestamp as `YYYY-MM-DD` (civil-from-days, no deps).
+fn format_date(secs: i64) -> String {
+    let days = secs.div_euclid(86_400);
+    // Howard Hinnant's days-from-civil, inverted.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// Merge a freshly rendered section into an existing changelog, inserting it
+/// above previously recorded releases while keeping the standard header/footer.
+fn merge(existing: &str, section: &str) -> String {
+    let body = existing
+        .strip_prefix(HEADER)
+        .unwrap_or(existing)
+        .trim_start_matches('\n');
+    let old = body.trim_end().strip_suffix(FOOTER).unwrap_or(body).trim_end();
+    if old.is_empty() {
+        format!("{HEADER}{section}\n{FOOTER}\n")
+    } else {
+        format!("{HEADER}{section}\n{old}\n\n{FOOTER}\n")
+    }
+}
+
+/// Render the collected entries into a single version section.
+fn render(entries: &[Entry], opts: &ChangelogOptions, date: &str) -> String {
+    let version = opts.to.as_deref().unwrap_or("Unreleased");
+
+    let mut out = String::new();
+    let _ = write!(out, "\n## [{version}] - {date}\n");
+
+    // Breaking changes are surfaced prominently at the top of the section.
+    let breaking: Vec<&Entry> = entries.iter().filter(|e| e.breaking).collect();
+    if !breaking.is_empty() {
+        out.push_str("\n### ⚠ BREAKING CHANGES\n\n");
+        for entry in breaking {
+            let _ = writeln!(out, "- {}", format_entry(entry));
+        }
+    }
+
+    // Then one section per known type, in render order, followed by "Other".
+    let order = SECTIONS
+        .iter()
+        .map(|(_, heading)| *heading)
+        .chain(std::iter::once(OTHER_HEADING));
+    for heading in order {
+        let section: Vec<&Entry> = entries.iter().filter(|e| e.heading == heading).collect();
+        if section.is_empty() {
+            continue;
+        }
+        let _ = write!(out, "\n### {heading}\n\n");
+        for entry in section {
+            let _ = writeln!(out, "- {}", format_entry(entry));
+        }
+    }
+
+    out
+}
+
+/// Format a single entry as `**scope:** description`, flagging breaking changes.
+fn format_entry(entry: &Entry) -> String {
+    let mut line = match &entry.scope {
+        Some(scope) => format!("**{scope}:** {}", entry.description),
+        None => entry.description.clone(),
+    };
+    if entry.breaking {
+        line.push_str(" **(BREAKING)**");
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge, FOOTER};
+
+    #[test]
+    fn repeated_merges_keep_a_single_footer() {
+        let once = merge("", "\n## [v1] - 2024-01-01\n");
+        let twice = merge(&once, "\n## [v2] - 2024-02-01\n");
+        assert_eq!(twice.matches(FOOTER).count(), 1);
+        // Both releases survive the incremental merge.
+        assert!(twice.contains("## [v1]"));
+        assert!(twice.contains("## [v2]"));
+    }
+}
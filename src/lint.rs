@@ -0,0 +1,279 @@
+//! Commit-message parsing and validation.
+//!
+//! Parses a generated message into a small AST — a subject line, the body
+//! paragraphs, and a trailing block of `Key: value` trailers — modelled on the
+//! `mit-commit` split into Subject/Bodies/Trailers, and enforces a Danger-style
+//! rule set before the message is used to create a commit.
+
+use std::fmt;
+
+/// A parsed commit message: subject line, body paragraphs, and trailers.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CommitMessage {
+    pub subject: String,
+    pub body: Vec<String>,
+    pub trailers: Vec<Trailer>,
+}
+
+/// A single `Key: value` trailer from the trailing block of a commit message.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Trailer {
+    pub key: String,
+    pub value: String,
+}
+
+impl fmt::Display for Trailer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.value)
+    }
+}
+
+/// A single rule violation, rendered for the user.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Violation(pub String);
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl CommitMessage {
+    /// Parse raw message text into subject, body paragraphs, and trailers.
+    ///
+    /// The first line is the subject. Everything after the first blank line is
+    /// the body, split into paragraphs on blank lines. If the final paragraph
+    /// consists solely of `Key: value` lines whose keys are recognised trailer
+    /// keys, it is treated as the trailer block.
+    pub fn parse(raw: &str) -> Self {
+        let mut lines = raw.trim_end().lines();
+        let subject = lines.next().unwrap_or_default().trim_end().to_string();
+
+        // Skip the blank separator between subject and body.
+        let rest: Vec<&str> = lines.collect();
+        let rest = match rest.split_first() {
+            Some((first, tail)) if first.trim().is_empty() => tail,
+            _ => &rest,
+        };
+
+        // Split the remainder into paragraphs on blank lines.
+        let mut paragraphs: Vec<String> = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        for line in rest {
+            if line.trim().is_empty() {
+                if !current.is_empty() {
+                    paragraphs.push(current.join("\n"));
+                    current.clear();
+                }
+            } else {
+                current.push(line);
+            }
+        }
+        if !current.is_empty() {
+            paragraphs.push(current.join("\n"));
+        }
+
+        // A final paragraph that is entirely trailers becomes the trailer block.
+        let mut trailers = Vec::new();
+        if let Some(last) = paragraphs.last() {
+            if let Some(parsed) = parse_trailer_block(last) {
+                trailers = parsed;
+                paragraphs.pop();
+            }
+        }
+
+        CommitMessage {
+            subject,
+            body: paragraphs,
+            trailers,
+        }
+    }
+
+    /// Append a trailer to the trailing block, de-duplicating against any
+    /// identical trailer the model may have already produced. Modelled on
+    /// `mit-commit`'s `add_trailer`: the block is kept as a run of `Key: value`
+    /// lines separated from the body by a blank line (handled by [`render`]).
+    ///
+    /// [`render`]: CommitMessage::render
+    pub fn add_trailer(&mut self, trailer: Trailer) {
+        let duplicate = self.trailers.iter().any(|existing| {
+            existing.key.eq_ignore_ascii_case(&trailer.key) && existing.value == trailer.value
+        });
+        if !duplicate {
+            self.trailers.push(trailer);
+        }
+    }
+
+    /// Render the message back to text: subject, a blank line and the body
+    /// paragraphs, then a blank line and the trailer block.
+    pub fn render(&self) -> String {
+        let mut out = self.subject.clone();
+        if !self.body.is_empty() {
+            out.push_str("\n\n");
+            out.push_str(&self.body.join("\n\n"));
+        }
+        if !self.trailers.is_empty() {
+            out.push_str("\n\n");
+            let block = self
+                .trailers
+                .iter()
+                .map(Trailer::to_string)
+                .collect::<Vec<_>>()
+                .join("\n");
+            out.push_str(&block);
+        }
+        out
+    }
+
+    /// Check the message against the rule set, returning every violation found.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        let subject_len = self.subject.chars().count();
+        if subject_len > 50 {
+            violations.push(Violation(format!(
+                "Subject is {subject_len} characters; keep it to 50 or fewer"
+            )));
+        }
+
+        if self.subject.ends_with('.') {
+            violations.push(Violation(
+                "Subject must not end with a period".to_string(),
+            ));
+        }
+
+        if contains_emoji(&self.subject) {
+            violations.push(Violation(
+                "Subject must not contain emoji".to_string(),
+            ));
+        }
+
+        // A Conventional Commits subject is lower-case by design, so only the
+        // plain-prose path is held to the capitalized-imperative rule.
+        if !is_conventional_subject(&self.subject) {
+            match self.subject.chars().next() {
+                Some(c) if c.is_uppercase() => {}
+                _ => violations.push(Violation(
+                    "Subject must start with a capitalized imperative verb".to_string(),
+                )),
+            }
+        }
+
+        for paragraph in &self.body {
+            for line in paragraph.lines() {
+                let len = line.chars().count();
+                if len > 72 {
+                    violations.push(Violation(format!(
+                        "Body line exceeds 72 columns ({len}): {line}"
+                    )));
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+/// Trailer keys the parser recognises. Restricting detection to these avoids
+/// swallowing a legitimate final body paragraph whose lines merely happen to
+/// look like `Key: value` (e.g. `Note: handle the empty case`).
+const KNOWN_TRAILER_KEYS: &[&str] = &[
+    "Signed-off-by",
+    "Co-authored-by",
+    "Reviewed-by",
+    "Acked-by",
+    "Tested-by",
+    "Reported-by",
+    "Suggested-by",
+    "Helped-by",
+    "BREAKING CHANGE",
+    "Closes",
+    "Fixes",
+    "Refs",
+    "Cc",
+];
+
+/// Parse a paragraph into trailers, returning `None` if any line is not a
+/// well-formed `Key: value` trailer with a recognised key.
+fn parse_trailer_block(paragraph: &str) -> Option<Vec<Trailer>> {
+    let mut trailers = Vec::new();
+    for line in paragraph.lines() {
+        let (key, value) = line.split_once(':')?;
+        let key = key.trim();
+        if key.is_empty() || (key.contains(' ') && key != "BREAKING CHANGE") {
+            return None;
+        }
+        if !KNOWN_TRAILER_KEYS
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(key))
+        {
+            return None;
+        }
+        trailers.push(Trailer {
+            key: key.to_string(),
+            value: value.trim().to_string(),
+        });
+    }
+    if trailers.is_empty() {
+        None
+    } else {
+        Some(trailers)
+    }
+}
+
+/// A Conventional Commits subject parsed into its parts.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Conventional {
+    pub type_: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+impl Conventional {
+    /// Parse a `<type>(<scope>)!: <description>` subject, returning `None` when
+    /// the type is not one of the known Conventional Commits types.
+    pub fn parse(subject: &str) -> Option<Self> {
+        let (prefix, description) = subject.split_once(": ")?;
+        let (prefix, breaking) = match prefix.strip_suffix('!') {
+            Some(stripped) => (stripped, true),
+            None => (prefix, false),
+        };
+
+        let (type_, scope) = match prefix.split_once('(') {
+            Some((ty, rest)) => (ty, rest.strip_suffix(')').map(str::to_string)),
+            None => (prefix, None),
+        };
+
+        if !crate::CONVENTIONAL_TYPES.contains(&type_) {
+            return None;
+        }
+
+        Some(Conventional {
+            type_: type_.to_string(),
+            scope,
+            breaking,
+            description: description.trim().to_string(),
+        })
+    }
+}
+
+/// Whether a subject is in Conventional Commits form (`type(scope)!: ...`).
+fn is_conventional_subject(subject: &str) -> bool {
+    Conventional::parse(subject).is_some()
+}
+
+/// Whether `s` contains a character in the common emoji ranges, matching the
+/// Danger emoji rule (U+1F300–1F5FF, U+1F600–1F64F, U+2600–26FF, plus the
+/// supplemental symbol and transport/dingbat blocks).
+fn contains_emoji(s: &str) -> bool {
+    s.chars().any(|c| {
+        let c = c as u32;
+        (0x1F300..=0x1F5FF).contains(&c)
+            || (0x1F600..=0x1F64F).contains(&c)
+            || (0x1F680..=0x1F6FF).contains(&c)
+            || (0x1F900..=0x1F9FF).contains(&c)
+            || (0x2600..=0x26FF).contains(&c)
+            || (0x2700..=0x27BF).contains(&c)
+    })
+}
@@ -1,9 +1,12 @@
-use clap::Parser;
+mod changelog;
+mod lint;
+mod provider;
+
+use clap::{Parser, Subcommand};
 use colored::Colorize;
 use dotenv::dotenv;
 use git2::{Repository, StatusOptions};
-use reqwest::blocking::Client;
-use serde::{Deserialize, Serialize};
+use provider::Provider;
 use std::env;
 use std::error::Error;
 use std::io::Write;
@@ -15,42 +18,111 @@ struct Args {
     /// Print verbose output
     #[clap(short, long)]
     verbose: bool,
-}
 
-#[derive(Serialize, Deserialize, Debug)]
-struct Message {
-    role: String,
-    content: String,
-}
+    /// Emit messages in Conventional Commits form (`<type>(<scope>): <description>`)
+    #[clap(long)]
+    conventional: bool,
 
-#[derive(Serialize, Deserialize, Debug)]
-struct AnthropicRequest {
-    model: String,
-    max_tokens: u32,
-    system: String,
-    messages: Vec<Message>,
-}
+    /// Validate an existing commit-message file against the lint rules and exit.
+    /// Exits non-zero on failure so it can back a `commit-msg` hook.
+    #[clap(long, value_name = "FILE")]
+    lint_only: Option<String>,
+
+    /// LLM provider to target: `anthropic`, `openai`, or `groq`. Falls back to
+    /// the `MSG_PROVIDER` env var, then `anthropic`.
+    #[clap(long, value_name = "PROVIDER")]
+    provider: Option<String>,
+
+    /// Override the provider's base URL (useful for proxies or self-hosted
+    /// OpenAI-compatible endpoints).
+    #[clap(long, value_name = "URL")]
+    base_url: Option<String>,
+
+    /// Sampling temperature passed to the provider.
+    #[clap(long)]
+    temperature: Option<f32>,
+
+    /// Append a `Signed-off-by` trailer using the git committer identity (DCO).
+    #[clap(long)]
+    sign_off: bool,
+
+    /// Append a `Co-authored-by` trailer. Repeatable; value is `Name <email>`.
+    #[clap(long, value_name = "NAME <EMAIL>")]
+    co_author: Vec<String>,
+
+    /// Commit-message file to edit. Set automatically when `msg` is invoked as
+    /// `GIT_EDITOR` (git passes the path to `.git/COMMIT_EDITMSG`).
+    #[clap(value_name = "COMMIT_EDITMSG")]
+    file: Option<String>,
 
-#[derive(Serialize, Deserialize, Debug)]
-struct AnthropicResponse {
-    content: Vec<ContentBlock>,
+    #[clap(subcommand)]
+    command: Option<Cmd>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct ContentBlock {
-    text: String,
-    #[serde(rename = "type")]
-    content_type: String,
+#[derive(Subcommand)]
+enum Cmd {
+    /// Generate or update CHANGELOG.md from conventional commit history.
+    Changelog {
+        /// Lower-bound ref/tag (exclusive) for an incremental update.
+        #[clap(long, value_name = "REF")]
+        from: Option<String>,
+
+        /// Upper-bound ref/tag. Defaults to HEAD and names the section.
+        #[clap(long, value_name = "REF")]
+        to: Option<String>,
+
+        /// Output path for the changelog.
+        #[clap(short, long, default_value = "CHANGELOG.md")]
+        output: String,
+    },
 }
 
+/// The standard Conventional Commits type set.
+const CONVENTIONAL_TYPES: &[&str] = &[
+    "feat", "fix", "refactor", "perf", "style", "test", "docs", "build", "ci", "chore",
+];
+
 fn main() -> Result<(), Box<dyn Error>> {
     // Load environment variables from .env file
     dotenv().ok();
 
-    let api_key = env::var("ANTHROPIC_API_KEY")
-        .expect("ANTHROPIC_API_KEY must be set in environment or .env file");
     let args = Args::parse();
 
+    // Subcommands run standalone and need neither an API key nor a staged diff.
+    if let Some(Cmd::Changelog { from, to, output }) = &args.command {
+        return changelog::run(&changelog::ChangelogOptions {
+            from: from.clone(),
+            to: to.clone(),
+            output: output.clone(),
+        });
+    }
+
+    // In `--lint-only` mode we validate an existing message file and exit,
+    // without needing an API key or a staged diff.
+    if let Some(path) = &args.lint_only {
+        let message = std::fs::read_to_string(path)?;
+        let violations = lint::CommitMessage::parse(&message).validate();
+        if violations.is_empty() {
+            println!("{}", "✅ Commit message passes lint".green().bold());
+            return Ok(());
+        }
+        report_violations(&violations);
+        std::process::exit(1);
+    }
+
+    let provider = provider::from_args(
+        args.provider.as_deref(),
+        args.base_url.as_deref(),
+        args.temperature,
+    )?;
+
+    // When invoked as `GIT_EDITOR`, git passes the path to `COMMIT_EDITMSG` as
+    // the sole positional argument. Fill the AI draft into that file and open the
+    // user's real editor on it for review before git reads it back.
+    if let Some(path) = args.file.as_ref().filter(|p| std::path::Path::new(p).is_file()) {
+        return run_as_editor(provider.as_ref(), path, args.conventional);
+    }
+
     // Try to open the repository at the current directory
     let repo = match Repository::open(".") {
         Ok(repo) => repo,
@@ -88,7 +160,51 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // Generate commit message using Claude API
     println!("{}", "Generating commit message...".blue());
-    let commit_message = get_claude_commit_message(&api_key, &diff)?;
+    let mut commit_message =
+        get_commit_message(provider.as_ref(), &diff, args.conventional, None)?;
+
+    // Validate against the lint rules, giving the user a chance to re-request a
+    // corrected message or edit it by hand before committing.
+    loop {
+        let violations = lint::CommitMessage::parse(&commit_message).validate();
+        if violations.is_empty() {
+            break;
+        }
+        report_violations(&violations);
+
+        print!(
+            "\n{} ",
+            "[r]e-request from the provider, [e]dit by hand, or [c]ommit anyway? [r/e/c]".cyan()
+        );
+        std::io::stdout().flush()?;
+        let mut choice = String::new();
+        std::io::stdin().read_line(&mut choice)?;
+        match choice.trim().to_lowercase().as_str() {
+            "r" => {
+                let feedback = violations
+                    .iter()
+                    .map(|v| format!("- {v}"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                println!("{}", "Re-requesting a corrected message...".blue());
+                commit_message = get_commit_message(
+                    provider.as_ref(),
+                    &diff,
+                    args.conventional,
+                    Some(&feedback),
+                )?;
+            }
+            "e" => {
+                commit_message = edit_message(&commit_message)?;
+            }
+            _ => break,
+        }
+    }
+
+    // Append any requested trailers (sign-off, co-authors) after the body.
+    if args.sign_off || !args.co_author.is_empty() {
+        commit_message = apply_trailers(&commit_message, &repo, args.sign_off, &args.co_author)?;
+    }
 
     println!(
         "\n{}\n\n{}",
@@ -123,6 +239,61 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Act as the commit editor: generate a message from the staged diff and write
+/// it above the existing comment block in `path`, preserving git's comment
+/// lines and the scissors (`# ----- >8 -----`) section so git's normal review
+/// flow still works, then open the file in the user's `$EDITOR` for review.
+fn run_as_editor(
+    provider: &dyn Provider,
+    path: &str,
+    conventional: bool,
+) -> Result<(), Box<dyn Error>> {
+    // Only the staged diff describes the commit git is about to make.
+    let output = Command::new("git").args(["diff", "--staged"]).output()?;
+    if !output.status.success() {
+        return Err("Failed to execute git diff".into());
+    }
+    let diff = String::from_utf8(output.stdout)?;
+    if diff.is_empty() {
+        // Nothing staged — leave git's template untouched.
+        return Ok(());
+    }
+
+    let existing = std::fs::read_to_string(path)?;
+    let message = get_commit_message(provider, &diff, conventional, None)?;
+
+    // Keep everything from git's template that is a comment or beyond the
+    // scissors line; the generated message replaces the blank editable region.
+    let scissors = "# ------------------------ >8 ------------------------";
+    let mut preserved: Vec<&str> = Vec::new();
+    let mut in_scissors = false;
+    for line in existing.lines() {
+        if line.starts_with(scissors) {
+            in_scissors = true;
+        }
+        if in_scissors || line.starts_with('#') {
+            preserved.push(line);
+        }
+    }
+
+    let mut contents = message;
+    if !preserved.is_empty() {
+        contents.push_str("\n\n");
+        contents.push_str(&preserved.join("\n"));
+        contents.push('\n');
+    }
+
+    std::fs::write(path, contents)?;
+
+    // Hand the draft to the user's `$EDITOR` (falling back to `vi`) for review.
+    let editor = env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let status = Command::new(&editor).arg(path).status()?;
+    if !status.success() {
+        return Err("Editor exited with a non-zero status".into());
+    }
+    Ok(())
+}
+
 fn get_git_diff(verbose: bool) -> Result<String, Box<dyn Error>> {
     // Get staged changes
     let output = Command::new("git").args(["diff", "--staged"]).output()?;
@@ -155,10 +326,133 @@ fn get_git_diff(verbose: bool) -> Result<String, Box<dyn Error>> {
     Ok(diff)
 }
 
-fn get_claude_commit_message(api_key: &str, diff: &str) -> Result<String, Box<dyn Error>> {
-    let client = Client::new();
+/// Infer a Conventional Commits scope from the files touched in a staged diff:
+/// the crate name under `crates/`, the module name under `src/`, or otherwise
+/// the top-level directory. Picks the most frequently changed component,
+/// falling back to `None` when nothing can be determined.
+fn infer_scope(diff: &str) -> Option<String> {
+    use std::collections::HashMap;
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for line in diff.lines() {
+        let Some(rest) = line.strip_prefix("diff --git a/") else {
+            continue;
+        };
+        // `a/<path> b/<path>` — take the first path and its leading component.
+        let Some(path) = rest.split(" b/").next() else {
+            continue;
+        };
+        // A workspace crate lives under `crates/<name>`; a flat single crate lays
+        // its modules out as `src/<module>.rs`. Unwrap the `crates/` prefix, then
+        // take the next segment as the scope, stripping any file extension so a
+        // module file like `src/foo.rs` yields `foo`.
+        let mut segments = path.split('/');
+        let candidate = match segments.next() {
+            Some("crates") => segments.next(),
+            Some("src") => segments.next(),
+            other => other,
+        };
+        let scope = candidate.map(|s| s.split('.').next().unwrap_or(s));
+        if let Some(scope) = scope.filter(|s| !s.is_empty()) {
+            *counts.entry(scope.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(scope, _)| scope)
+}
+
+/// Build the system prompt that steers the model toward the Conventional
+/// Commits format, optionally seeding a detected `scope`.
+fn conventional_system_message(scope: &Option<String>) -> String {
+    let scope_hint = match scope {
+        Some(scope) => format!(
+            "Use `{scope}` as the scope unless the diff clearly belongs to a different component."
+        ),
+        None => "Infer a short scope from the changed files, or omit the scope entirely if no single component dominates.".to_string(),
+    };
+
+    format!(
+        "Generate git commit messages from diffs in the Conventional Commits format. \
+         Guidelines:\
+         1. Format the subject as `<type>(<scope>): <description>` (the scope and its parentheses are optional).\
+         2. The type must be one of: {types}.\
+         3. {scope_hint}\
+         4. The description is a concise imperative phrase, lower-case, with no trailing period.\
+         5. Optionally follow with a blank line and a body explaining the change, then a blank line and any footers.\
+         6. If the diff removes or changes public APIs, append `!` after the type/scope (e.g. `feat(api)!: ...`) and add a `BREAKING CHANGE: <explanation>` footer.\
+         7. Return only the formatted commit message with no commentary.",
+        types = CONVENTIONAL_TYPES.join(", "),
+    )
+}
+
+/// Append `Signed-off-by` and `Co-authored-by` trailers to a message, pulling
+/// the committer identity from git config for the sign-off and de-duplicating
+/// against trailers the model may have already emitted.
+fn apply_trailers(
+    message: &str,
+    repo: &Repository,
+    sign_off: bool,
+    co_authors: &[String],
+) -> Result<String, Box<dyn Error>> {
+    let mut parsed = lint::CommitMessage::parse(message);
+
+    for co_author in co_authors {
+        parsed.add_trailer(lint::Trailer {
+            key: "Co-authored-by".to_string(),
+            value: co_author.trim().to_string(),
+        });
+    }
+
+    if sign_off {
+        let signature = repo.signature()?;
+        let name = signature.name().unwrap_or_default();
+        let email = signature.email().unwrap_or_default();
+        parsed.add_trailer(lint::Trailer {
+            key: "Signed-off-by".to_string(),
+            value: format!("{name} <{email}>"),
+        });
+    }
+
+    Ok(parsed.render())
+}
+
+/// Print each lint violation in red so failures are easy to spot.
+fn report_violations(violations: &[lint::Violation]) {
+    eprintln!(
+        "{}",
+        "Commit message failed validation:".bright_red().bold()
+    );
+    for violation in violations {
+        eprintln!("{} {}", "✗".bright_red(), violation.to_string().red());
+    }
+}
+
+/// Open the message in the user's `$EDITOR` (falling back to `vi`) and return
+/// the edited contents.
+fn edit_message(message: &str) -> Result<String, Box<dyn Error>> {
+    let path = env::temp_dir().join("MSG_EDITMSG");
+    std::fs::write(&path, message)?;
 
-    let system_message = "Generate git commit messages from diffs. \
+    let editor = env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let status = Command::new(&editor).arg(&path).status()?;
+    if !status.success() {
+        return Err("Editor exited with a non-zero status".into());
+    }
+
+    let edited = std::fs::read_to_string(&path)?;
+    Ok(edited.trim().to_string())
+}
+
+fn get_commit_message(
+    provider: &dyn Provider,
+    diff: &str,
+    conventional: bool,
+    feedback: Option<&str>,
+) -> Result<String, Box<dyn Error>> {
+    let default_system = "Generate git commit messages from diffs. \
                          Guidelines:\
                          1. Start with imperative verb (Add, Fix, Update, etc.)\
                          2. Format as a concise title line (under 50 characters)\
@@ -168,51 +462,42 @@ fn get_claude_commit_message(api_key: &str, diff: &str) -> Result<String, Box<dy
                          6. Focus on technical changes, not why they're beneficial\
                          7. Don't include a '## Changes' section\
                          8. Return only the formatted commit message with no commentary\
-                         9. The title line should never be prefixed with #";
+                         9. The title line should never be prefixed with #"
+        .to_string();
 
-    let user_message = format!(
+    let system_message = if conventional {
+        conventional_system_message(&infer_scope(diff))
+    } else {
+        default_system
+    };
+
+    let mut user_message = format!(
         "Generate a commit message for the following git diff:\n\n```\n{}\n```",
         diff
     );
+    if let Some(feedback) = feedback {
+        user_message.push_str(&format!(
+            "\n\nThe previous message failed these validation rules — \
+             fix them and return a corrected message:\n{feedback}"
+        ));
+    }
 
-    let request = AnthropicRequest {
-        model: "claude-3-7-sonnet-20250219".to_string(),
-        max_tokens: 1000,
-        system: system_message.to_string(),
-        messages: vec![Message {
-            role: "user".to_string(),
-            content: user_message,
-        }],
-    };
+    provider.complete(&system_message, &user_message)
+}
 
-    let response = client
-        .post("https://api.anthropic.com/v1/messages")
-        .header("x-api-key", api_key)
-        .header("anthropic-version", "2023-06-01")
-        .header("content-type", "application/json")
-        .json(&request)
-        .send()?;
-
-    if !response.status().is_success() {
-        let error_text = response.text()?;
-        return Err(format!(
-            "{}: {}",
-            "API request failed".bright_red().bold(),
-            error_text
-        )
-        .into());
-    }
+#[cfg(test)]
+mod tests {
+    use super::infer_scope;
 
-    let response_data: AnthropicResponse = response.json()?;
+    #[test]
+    fn infers_module_scope_for_flat_src_layout() {
+        let diff = "diff --git a/src/foo.rs b/src/foo.rs\n+changed\n";
+        assert_eq!(infer_scope(diff), Some("foo".to_string()));
+    }
 
-    // Get text from the first content block
-    if let Some(content_block) = response_data.content.first() {
-        Ok(content_block.text.trim().to_string())
-    } else {
-        Err(format!(
-            "{}",
-            "No content received from Claude API".bright_red().bold()
-        )
-        .into())
+    #[test]
+    fn infers_crate_scope_under_crates_prefix() {
+        let diff = "diff --git a/crates/parser/src/lib.rs b/crates/parser/src/lib.rs\n";
+        assert_eq!(infer_scope(diff), Some("parser".to_string()));
     }
 }
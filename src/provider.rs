@@ -0,0 +1,247 @@
+//! LLM provider abstraction.
+//!
+//! A [`Provider`] turns a system/user prompt pair into a commit-message string.
+//! The Anthropic Messages API and the OpenAI-compatible chat-completions API
+//! (also used by Groq) build their own request JSON and parse their own
+//! response shape, but both return the same `String` so the rest of the tool is
+//! provider-agnostic.
+
+use colored::Colorize;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::error::Error;
+
+/// The set of supported providers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    Anthropic,
+    OpenAi,
+    Groq,
+}
+
+impl ProviderKind {
+    fn parse(name: &str) -> Result<Self, Box<dyn Error>> {
+        match name.to_lowercase().as_str() {
+            "anthropic" => Ok(ProviderKind::Anthropic),
+            "openai" => Ok(ProviderKind::OpenAi),
+            "groq" => Ok(ProviderKind::Groq),
+            other => Err(format!("Unknown provider: {other}").into()),
+        }
+    }
+
+    fn api_key_env(self) -> &'static str {
+        match self {
+            ProviderKind::Anthropic => "ANTHROPIC_API_KEY",
+            ProviderKind::OpenAi => "OPENAI_API_KEY",
+            ProviderKind::Groq => "GROQ_API_KEY",
+        }
+    }
+
+    fn default_model(self) -> &'static str {
+        match self {
+            ProviderKind::Anthropic => "claude-3-7-sonnet-20250219",
+            ProviderKind::OpenAi => "gpt-4o",
+            ProviderKind::Groq => "llama-3.1-70b-versatile",
+        }
+    }
+
+    fn default_base_url(self) -> &'static str {
+        match self {
+            ProviderKind::Anthropic => "https://api.anthropic.com",
+            ProviderKind::OpenAi => "https://api.openai.com",
+            ProviderKind::Groq => "https://api.groq.com/openai",
+        }
+    }
+}
+
+/// Build a provider from the selected kind, an optional base-URL override, and
+/// an optional temperature, pulling the API key from the provider's env var.
+///
+/// `name` comes from the `--provider` flag, falling back to `MSG_PROVIDER`, and
+/// defaulting to `anthropic`.
+pub fn from_args(
+    name: Option<&str>,
+    base_url: Option<&str>,
+    temperature: Option<f32>,
+) -> Result<Box<dyn Provider>, Box<dyn Error>> {
+    let name = name
+        .map(str::to_string)
+        .or_else(|| env::var("MSG_PROVIDER").ok())
+        .unwrap_or_else(|| "anthropic".to_string());
+    let kind = ProviderKind::parse(&name)?;
+
+    let api_key = env::var(kind.api_key_env()).map_err(|_| {
+        format!(
+            "{} must be set in environment or .env file",
+            kind.api_key_env()
+        )
+    })?;
+    let base_url = base_url
+        .map(str::to_string)
+        .unwrap_or_else(|| kind.default_base_url().to_string());
+    let model = kind.default_model().to_string();
+
+    let config = Config {
+        api_key,
+        model,
+        base_url,
+        temperature,
+    };
+
+    Ok(match kind {
+        ProviderKind::Anthropic => Box::new(AnthropicProvider(config)),
+        ProviderKind::OpenAi | ProviderKind::Groq => Box::new(OpenAiProvider(config)),
+    })
+}
+
+/// Shared per-provider configuration.
+struct Config {
+    api_key: String,
+    model: String,
+    base_url: String,
+    temperature: Option<f32>,
+}
+
+/// A provider turns a system/user prompt pair into a completion string.
+pub trait Provider {
+    fn complete(&self, system: &str, user: &str) -> Result<String, Box<dyn Error>>;
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+// --- Anthropic ------------------------------------------------------------
+
+#[derive(Serialize, Deserialize, Debug)]
+struct AnthropicRequest {
+    model: String,
+    max_tokens: u32,
+    system: String,
+    messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct AnthropicResponse {
+    content: Vec<ContentBlock>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ContentBlock {
+    text: String,
+    #[serde(rename = "type")]
+    content_type: String,
+}
+
+struct AnthropicProvider(Config);
+
+impl Provider for AnthropicProvider {
+    fn complete(&self, system: &str, user: &str) -> Result<String, Box<dyn Error>> {
+        let client = Client::new();
+        let request = AnthropicRequest {
+            model: self.0.model.clone(),
+            max_tokens: 1000,
+            system: system.to_string(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: user.to_string(),
+            }],
+            temperature: self.0.temperature,
+        };
+
+        let response = client
+            .post(format!("{}/v1/messages", self.0.base_url))
+            .header("x-api-key", &self.0.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&request)
+            .send()?;
+
+        let response = check_status(response)?;
+        let data: AnthropicResponse = response.json()?;
+        data.content
+            .first()
+            .map(|block| block.text.trim().to_string())
+            .ok_or_else(no_content_error)
+    }
+}
+
+// --- OpenAI-compatible (OpenAI, Groq) -------------------------------------
+
+#[derive(Serialize, Deserialize, Debug)]
+struct OpenAiRequest {
+    model: String,
+    messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct OpenAiResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Choice {
+    message: Message,
+}
+
+struct OpenAiProvider(Config);
+
+impl Provider for OpenAiProvider {
+    fn complete(&self, system: &str, user: &str) -> Result<String, Box<dyn Error>> {
+        let client = Client::new();
+        let request = OpenAiRequest {
+            model: self.0.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: system.to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: user.to_string(),
+                },
+            ],
+            temperature: self.0.temperature,
+        };
+
+        let response = client
+            .post(format!("{}/v1/chat/completions", self.0.base_url))
+            .header("authorization", format!("Bearer {}", self.0.api_key))
+            .header("content-type", "application/json")
+            .json(&request)
+            .send()?;
+
+        let response = check_status(response)?;
+        let data: OpenAiResponse = response.json()?;
+        data.choices
+            .first()
+            .map(|choice| choice.message.content.trim().to_string())
+            .ok_or_else(no_content_error)
+    }
+}
+
+fn check_status(
+    response: reqwest::blocking::Response,
+) -> Result<reqwest::blocking::Response, Box<dyn Error>> {
+    if !response.status().is_success() {
+        let error_text = response.text()?;
+        return Err(format!(
+            "{}: {}",
+            "API request failed".bright_red().bold(),
+            error_text
+        )
+        .into());
+    }
+    Ok(response)
+}
+
+fn no_content_error() -> Box<dyn Error> {
+    format!("{}", "No content received from provider".bright_red().bold()).into()
+}